@@ -0,0 +1,177 @@
+//! Generates and updates `CHANGELOG.md` from conventional-commit history.
+
+use crate::git::Git;
+use std::fs;
+use std::path::Path;
+
+const DEFAULT_HEADER: &str =
+    "# Changelog\n\nAll notable changes to this project will be documented in this file.\n";
+const DEFAULT_FOOTER: &str = "";
+
+/// A single commit reduced to the pieces the changelog cares about.
+struct ParsedCommit {
+    commit_type: String,
+    breaking: bool,
+    breaking_note: Option<String>,
+    description: String,
+}
+
+/// Commit types rendered as changelog sections, in display order.
+/// Types not listed here (e.g. `chore`, `ci`) get no section of their own;
+/// a breaking commit of any type still appears under BREAKING CHANGES.
+const SECTIONS: &[(&str, &str)] = &[
+    ("feat", "Features"),
+    ("fix", "Bug Fixes"),
+    ("perf", "Performance"),
+    ("refactor", "Refactors"),
+    ("docs", "Documentation"),
+];
+
+fn parse_commit(message: &str) -> Option<ParsedCommit> {
+    let mut lines = message.lines();
+    let header = lines.next()?;
+    let (type_and_scope, description) = header.split_once(':')?;
+    let description = description.trim().to_string();
+    if description.is_empty() {
+        return None;
+    }
+
+    let breaking_bang = type_and_scope.trim_end().ends_with('!');
+    let commit_type = type_and_scope
+        .trim_end_matches('!')
+        .split('(')
+        .next()
+        .unwrap_or(type_and_scope)
+        .trim()
+        .to_string();
+
+    let rest: Vec<&str> = lines.collect();
+    let breaking_note = rest
+        .iter()
+        .find(|line| line.starts_with("BREAKING CHANGE:"))
+        .map(|line| line.trim_start_matches("BREAKING CHANGE:").trim().to_string());
+
+    Some(ParsedCommit {
+        commit_type,
+        breaking: breaking_bang || breaking_note.is_some(),
+        breaking_note,
+        description,
+    })
+}
+
+/// Renders a Markdown block for the given commits; does not touch disk.
+fn render(commits: &[ParsedCommit]) -> String {
+    let mut out = String::new();
+
+    let breaking: Vec<&ParsedCommit> = commits.iter().filter(|c| c.breaking).collect();
+    if !breaking.is_empty() {
+        out.push_str("## ⚠ BREAKING CHANGES\n\n");
+        for commit in &breaking {
+            let note = commit.breaking_note.as_deref().unwrap_or(&commit.description);
+            out.push_str(&format!("- {}\n", note));
+        }
+        out.push('\n');
+    }
+
+    for (commit_type, title) in SECTIONS {
+        let matching: Vec<&ParsedCommit> = commits
+            .iter()
+            .filter(|c| &c.commit_type == commit_type)
+            .collect();
+        if matching.is_empty() {
+            continue;
+        }
+        out.push_str(&format!("## {}\n\n", title));
+        for commit in matching {
+            out.push_str(&format!("- {}\n", commit.description));
+        }
+        out.push('\n');
+    }
+
+    out
+}
+
+/// Reads commits since the last tag, groups them, and prepends the
+/// resulting Markdown block above any existing `CHANGELOG.md` content.
+/// `header`/`footer` default to the built-in boilerplate when empty, so
+/// `.ga.toml` can override either independently. Goes through the `Git`
+/// trait rather than shelling out directly, so this benefits from the
+/// same `git2`-first robustness as the rest of the CLI.
+pub fn update(repo: &dyn Git, path: &Path, header: &str, footer: &str) -> Result<(), String> {
+    let header = if header.is_empty() { DEFAULT_HEADER } else { header };
+    let footer = if footer.is_empty() { DEFAULT_FOOTER } else { footer };
+
+    let tag = repo.last_tag();
+    let messages = repo.commits_since(tag.as_deref())?;
+    let commits: Vec<ParsedCommit> = messages.iter().filter_map(|m| parse_commit(m)).collect();
+
+    if commits.is_empty() {
+        return Err("No conventional commits found since the last tag".to_string());
+    }
+
+    let block = render(&commits);
+    let existing = fs::read_to_string(path).unwrap_or_default();
+
+    let mut new_content = String::new();
+    new_content.push_str(header);
+    new_content.push('\n');
+    new_content.push_str(&block);
+    if !footer.is_empty() {
+        new_content.push_str(footer);
+        new_content.push('\n');
+    }
+
+    if !existing.is_empty() {
+        let body = existing
+            .strip_prefix(header)
+            .unwrap_or(&existing)
+            .trim_start_matches('\n');
+        new_content.push_str(body);
+    }
+
+    fs::write(path, new_content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_feature_commit() {
+        let commit = parse_commit("feat(cli): add changelog command").unwrap();
+        assert_eq!(commit.commit_type, "feat");
+        assert!(!commit.breaking);
+        assert_eq!(commit.description, "add changelog command");
+    }
+
+    #[test]
+    fn detects_bang_breaking_change() {
+        let commit = parse_commit("feat!: remove legacy flag").unwrap();
+        assert!(commit.breaking);
+    }
+
+    #[test]
+    fn detects_breaking_change_footer() {
+        let commit = parse_commit("fix: correct push logic\n\nBREAKING CHANGE: renames --origin").unwrap();
+        assert!(commit.breaking);
+        assert_eq!(commit.breaking_note.as_deref(), Some("renames --origin"));
+    }
+
+    #[test]
+    fn groups_commits_into_sections() {
+        let commits = vec![
+            parse_commit("feat: add x").unwrap(),
+            parse_commit("fix: correct y").unwrap(),
+        ];
+        let block = render(&commits);
+        assert!(block.contains("## Features"));
+        assert!(block.contains("## Bug Fixes"));
+    }
+
+    #[test]
+    fn ignores_commits_with_unrecognized_type() {
+        let commits = vec![parse_commit("chore: bump deps").unwrap()];
+        let block = render(&commits);
+        assert!(block.is_empty());
+    }
+}
@@ -0,0 +1,360 @@
+//! Conventional-commit message validation and an interactive builder
+//! used when no `--message` is supplied on the command line.
+
+use dialoguer::{Input, Select};
+
+/// Commit types accepted by the default rule set.
+///
+/// Mirrors the Angular/conventional-commits convention most teams already
+/// follow, so `ga` doesn't surprise anyone coming from another tool.
+const ALLOWED_TYPES: &[&str] = &[
+    "feat", "fix", "docs", "style", "refactor", "perf", "test", "build", "ci", "chore", "revert",
+];
+
+const DEFAULT_MAX_HEADER_LEN: usize = 72;
+
+/// A single rule violation, reported with enough context to fix it without
+/// re-reading the conventional-commits spec.
+#[derive(Debug, PartialEq, Eq)]
+pub struct LintError {
+    pub rule: String,
+    pub message: String,
+}
+
+impl std::fmt::Display for LintError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[{}] {}", self.rule, self.message)
+    }
+}
+
+/// A parsed conventional-commit header: `type(scope)!: description`.
+#[derive(Debug, PartialEq, Eq)]
+struct Header {
+    commit_type: String,
+    scope: Option<String>,
+    breaking: bool,
+    description: String,
+}
+
+fn parse_header(header: &str) -> Result<Header, LintError> {
+    let (type_and_scope, description) = header.split_once(':').ok_or_else(|| LintError {
+        rule: "format".to_string(),
+        message: "header must be of the form `type(scope)!: description`".to_string(),
+    })?;
+
+    let description = description.trim();
+    if description.is_empty() {
+        return Err(LintError {
+            rule: "format".to_string(),
+            message: "description must not be empty".to_string(),
+        });
+    }
+    let (type_and_scope, breaking) = match type_and_scope.strip_suffix('!') {
+        Some(rest) => (rest, true),
+        None => (type_and_scope, false),
+    };
+
+    let (commit_type, scope) = match type_and_scope.split_once('(') {
+        Some((t, rest)) => {
+            let scope = rest.strip_suffix(')').ok_or_else(|| LintError {
+                rule: "format".to_string(),
+                message: "scope must be closed with `)`".to_string(),
+            })?;
+            if scope.is_empty() {
+                return Err(LintError {
+                    rule: "format".to_string(),
+                    message: "scope must not be empty when parentheses are present".to_string(),
+                });
+            }
+            (t.to_string(), Some(scope.to_string()))
+        }
+        None => (type_and_scope.to_string(), None),
+    };
+
+    Ok(Header {
+        commit_type,
+        scope,
+        breaking,
+        description: description.to_string(),
+    })
+}
+
+fn check_type_whitelist(header: &Header, allowed_types: &[String]) -> Result<(), LintError> {
+    if allowed_types.iter().any(|t| t == &header.commit_type) {
+        Ok(())
+    } else {
+        Err(LintError {
+            rule: "type".to_string(),
+            message: format!(
+                "unknown commit type `{}` (allowed: {})",
+                header.commit_type,
+                allowed_types.join(", ")
+            ),
+        })
+    }
+}
+
+fn check_header_length(header_line: &str, max_len: usize) -> Result<(), LintError> {
+    if header_line.len() > max_len {
+        Err(LintError {
+            rule: "header-length".to_string(),
+            message: format!(
+                "header is {} characters, maximum is {}",
+                header_line.len(),
+                max_len
+            ),
+        })
+    } else {
+        Ok(())
+    }
+}
+
+/// Common non-verb openers that end in a bare `s` but aren't third-person
+/// verbs, so they don't trip the imperative-mood heuristic below (e.g.
+/// "fix: always validate input", "fix: focus error handling").
+const NON_VERB_S_WORDS: &[&str] = &["always", "focus", "this", "status", "across"];
+
+/// Rejects descriptions that read like past tense or third person
+/// ("added", "adds") rather than the imperative mood conventional
+/// commits expects ("add").
+fn check_imperative_mood(header: &Header) -> Result<(), LintError> {
+    let first_word = header
+        .description
+        .split_whitespace()
+        .next()
+        .unwrap_or_default();
+    let first_word_lower = first_word.to_lowercase();
+
+    let looks_like_third_person_verb = first_word.ends_with('s')
+        && !first_word.ends_with("ss")
+        && !NON_VERB_S_WORDS.contains(&first_word_lower.as_str());
+
+    if first_word.ends_with("ed") || looks_like_third_person_verb {
+        Err(LintError {
+            rule: "imperative-mood".to_string(),
+            message: format!(
+                "description should use the imperative mood, e.g. \"add\" not \"{}\"",
+                first_word
+            ),
+        })
+    } else {
+        Ok(())
+    }
+}
+
+fn check_blank_line_before_body(lines: &[&str]) -> Result<(), LintError> {
+    if lines.len() > 1 && !lines[1].is_empty() {
+        Err(LintError {
+            rule: "blank-line".to_string(),
+            message: "there must be a blank line between the header and the body".to_string(),
+        })
+    } else {
+        Ok(())
+    }
+}
+
+fn check_body_line_wrapping(lines: &[&str], max_len: usize) -> Result<(), LintError> {
+    for line in lines.iter().skip(2) {
+        if line.len() > max_len {
+            return Err(LintError {
+                rule: "body-wrap".to_string(),
+                message: format!(
+                    "body line is {} characters, wrap at {}",
+                    line.len(),
+                    max_len
+                ),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Rules that govern a valid conventional commit message.
+///
+/// All fields have sensible defaults and can be overridden from
+/// `.ga.toml` via [`LintRules::from_config`].
+pub struct LintRules {
+    pub allowed_types: Vec<String>,
+    pub max_header_len: usize,
+    pub body_wrap_len: usize,
+}
+
+impl Default for LintRules {
+    fn default() -> Self {
+        Self {
+            allowed_types: ALLOWED_TYPES.iter().map(|s| s.to_string()).collect(),
+            max_header_len: DEFAULT_MAX_HEADER_LEN,
+            body_wrap_len: DEFAULT_MAX_HEADER_LEN,
+        }
+    }
+}
+
+impl LintRules {
+    /// Applies overrides from `.ga.toml`'s `[lint]` table over the defaults.
+    pub fn from_config(config: &crate::config::LintConfig) -> Self {
+        let defaults = Self::default();
+        Self {
+            allowed_types: config
+                .allowed_types
+                .clone()
+                .unwrap_or(defaults.allowed_types),
+            max_header_len: config.max_header_len.unwrap_or(defaults.max_header_len),
+            body_wrap_len: config.body_wrap_len.unwrap_or(defaults.body_wrap_len),
+        }
+    }
+}
+
+/// Validates `message` against `rules`, returning every violation found
+/// rather than stopping at the first one.
+pub fn lint(message: &str, rules: &LintRules) -> Vec<LintError> {
+    let mut errors = Vec::new();
+    let lines: Vec<&str> = message.lines().collect();
+    let header_line = lines.first().copied().unwrap_or_default();
+
+    if let Err(e) = check_header_length(header_line, rules.max_header_len) {
+        errors.push(e);
+    }
+
+    match parse_header(header_line) {
+        Ok(header) => {
+            if let Err(e) = check_type_whitelist(&header, &rules.allowed_types) {
+                errors.push(e);
+            }
+            if let Err(e) = check_imperative_mood(&header) {
+                errors.push(e);
+            }
+        }
+        Err(e) => errors.push(e),
+    }
+
+    if let Err(e) = check_blank_line_before_body(&lines) {
+        errors.push(e);
+    }
+    if let Err(e) = check_body_line_wrapping(&lines, rules.body_wrap_len) {
+        errors.push(e);
+    }
+
+    errors
+}
+
+/// Prompts separately for type, scope, description, and body, then
+/// assembles a conventional-commit message. The type is picked from
+/// `rules.allowed_types` (so a team's `.ga.toml` type whitelist is honored
+/// instead of the hardcoded defaults); the description's mood, the header
+/// length and the body wrapping are not checked here, so the result can
+/// still fail `lint`.
+pub fn build_message_interactively(rules: &LintRules) -> Result<String, String> {
+    let type_idx = Select::new()
+        .with_prompt("Commit type")
+        .items(&rules.allowed_types)
+        .default(0)
+        .interact()
+        .map_err(|e| format!("Failed to read commit type: {}", e))?;
+    let commit_type = &rules.allowed_types[type_idx];
+
+    let scope: String = Input::new()
+        .with_prompt("Scope (optional)")
+        .allow_empty(true)
+        .interact_text()
+        .map_err(|e| format!("Failed to read scope: {}", e))?;
+
+    let description: String = Input::new()
+        .with_prompt("Short description")
+        .interact_text()
+        .map_err(|e| format!("Failed to read description: {}", e))?;
+    if description.trim().is_empty() {
+        return Err("Description cannot be empty".to_string());
+    }
+
+    let body: String = Input::new()
+        .with_prompt("Body (optional)")
+        .allow_empty(true)
+        .interact_text()
+        .map_err(|e| format!("Failed to read body: {}", e))?;
+
+    let header = if scope.trim().is_empty() {
+        format!("{}: {}", commit_type, description.trim())
+    } else {
+        format!("{}({}): {}", commit_type, scope.trim(), description.trim())
+    };
+
+    let message = if body.trim().is_empty() {
+        header
+    } else {
+        format!("{}\n\n{}", header, body.trim())
+    };
+
+    Ok(message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_type_scope_breaking_and_description() {
+        let header = parse_header("feat(cli)!: add changelog command").unwrap();
+        assert_eq!(header.commit_type, "feat");
+        assert_eq!(header.scope.as_deref(), Some("cli"));
+        assert!(header.breaking);
+        assert_eq!(header.description, "add changelog command");
+    }
+
+    #[test]
+    fn rejects_header_without_colon() {
+        assert!(parse_header("add a feature").is_err());
+    }
+
+    #[test]
+    fn rejects_unknown_type() {
+        let rules = LintRules::default();
+        let errors = lint("feet: add a thing", &rules);
+        assert!(errors.iter().any(|e| e.rule == "type"));
+    }
+
+    #[test]
+    fn rejects_past_tense_description() {
+        let rules = LintRules::default();
+        let errors = lint("fix: added a thing", &rules);
+        assert!(errors.iter().any(|e| e.rule == "imperative-mood"));
+    }
+
+    #[test]
+    fn rejects_third_person_description() {
+        let rules = LintRules::default();
+        let errors = lint("fix: handles the edge case", &rules);
+        assert!(errors.iter().any(|e| e.rule == "imperative-mood"));
+    }
+
+    #[test]
+    fn accepts_non_verb_openers_ending_in_s() {
+        let rules = LintRules::default();
+        let errors = lint("fix: always validate input", &rules);
+        assert!(!errors.iter().any(|e| e.rule == "imperative-mood"));
+
+        let errors = lint("fix: focus error handling on the happy path", &rules);
+        assert!(!errors.iter().any(|e| e.rule == "imperative-mood"));
+    }
+
+    #[test]
+    fn rejects_missing_blank_line_before_body() {
+        let rules = LintRules::default();
+        let errors = lint("fix: correct typo\nthis is the body", &rules);
+        assert!(errors.iter().any(|e| e.rule == "blank-line"));
+    }
+
+    #[test]
+    fn accepts_well_formed_message() {
+        let rules = LintRules::default();
+        let errors = lint("fix: correct typo in readme\n\nThis clarifies install steps.", &rules);
+        assert!(errors.is_empty());
+    }
+
+    #[test]
+    fn rejects_header_over_max_length() {
+        let rules = LintRules::default();
+        let long = format!("fix: {}", "a".repeat(100));
+        let errors = lint(&long, &rules);
+        assert!(errors.iter().any(|e| e.rule == "header-length"));
+    }
+}
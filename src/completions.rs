@@ -0,0 +1,15 @@
+//! Shell completion script generation, driven by the `Args` clap definition
+//! so the emitted script never drifts from the actual CLI surface.
+
+use clap::CommandFactory;
+use clap_complete::Shell;
+use std::io;
+
+use crate::Args;
+
+/// Writes a completion script for `shell` to stdout.
+pub fn print(shell: Shell) {
+    let mut cmd = Args::command();
+    let name = cmd.get_name().to_string();
+    clap_complete::generate(shell, &mut cmd, name, &mut io::stdout());
+}
@@ -0,0 +1,148 @@
+//! Loads `.ga.toml` configuration so teams can standardize `ga` behavior
+//! without everyone memorizing flags.
+//!
+//! Precedence (highest to lowest): CLI flag > repo config > user config >
+//! built-in default. This module only produces the repo/user layer;
+//! merging in CLI flags is the caller's job since `clap::Args` doesn't
+//! know about this file.
+
+use serde::Deserialize;
+use std::path::PathBuf;
+
+#[derive(Debug, Deserialize, Default, Clone)]
+pub struct Config {
+    pub remote: Option<String>,
+    pub branch: Option<String>,
+    pub sign: Option<bool>,
+    pub verbose: Option<bool>,
+    #[serde(default)]
+    pub lint: LintConfig,
+    #[serde(default)]
+    pub changelog: ChangelogConfig,
+    #[serde(default)]
+    pub email: EmailConfig,
+    #[serde(default)]
+    pub pr: PrConfig,
+}
+
+#[derive(Debug, Deserialize, Default, Clone)]
+pub struct LintConfig {
+    pub allowed_types: Option<Vec<String>>,
+    pub max_header_len: Option<usize>,
+    pub body_wrap_len: Option<usize>,
+}
+
+#[derive(Debug, Deserialize, Default, Clone)]
+pub struct ChangelogConfig {
+    pub header: Option<String>,
+    pub footer: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Default, Clone)]
+pub struct EmailConfig {
+    pub from: Option<String>,
+    pub to: Option<Vec<String>>,
+}
+
+#[derive(Debug, Deserialize, Default, Clone)]
+pub struct PrConfig {
+    /// Name of the environment variable holding the host API token.
+    /// Defaults to `GITHUB_TOKEN`.
+    pub token_env: Option<String>,
+    /// Target branch for opened pull requests. Defaults to the remote's
+    /// default branch when unset.
+    pub base: Option<String>,
+}
+
+impl Config {
+    /// Fills in any field left unset by `self` with the corresponding
+    /// field from `lower`, which has lower precedence.
+    fn merge(self, lower: Config) -> Config {
+        Config {
+            remote: self.remote.or(lower.remote),
+            branch: self.branch.or(lower.branch),
+            sign: self.sign.or(lower.sign),
+            verbose: self.verbose.or(lower.verbose),
+            lint: LintConfig {
+                allowed_types: self.lint.allowed_types.or(lower.lint.allowed_types),
+                max_header_len: self.lint.max_header_len.or(lower.lint.max_header_len),
+                body_wrap_len: self.lint.body_wrap_len.or(lower.lint.body_wrap_len),
+            },
+            changelog: ChangelogConfig {
+                header: self.changelog.header.or(lower.changelog.header),
+                footer: self.changelog.footer.or(lower.changelog.footer),
+            },
+            email: EmailConfig {
+                from: self.email.from.or(lower.email.from),
+                to: self.email.to.or(lower.email.to),
+            },
+            pr: PrConfig {
+                token_env: self.pr.token_env.or(lower.pr.token_env),
+                base: self.pr.base.or(lower.pr.base),
+            },
+        }
+    }
+}
+
+fn user_config_path() -> Option<PathBuf> {
+    let base = std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| dirs::home_dir().map(|home| home.join(".config")))?;
+    Some(base.join("ga").join("config.toml"))
+}
+
+fn read_config(path: &std::path::Path) -> Config {
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|contents| toml::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
+/// Loads and merges the repo (`.ga.toml`) and user config, repo winning.
+/// Missing files are treated as empty config, not an error. `.ga.toml` is
+/// searched for at the repository's work tree root, not the process's
+/// current directory, so it's found when `ga` runs from a subdirectory.
+pub fn load() -> Config {
+    let repo_root = crate::git::discover_root();
+    let repo_config_path = match &repo_root {
+        Some(root) => root.join(".ga.toml"),
+        None => PathBuf::from(".ga.toml"),
+    };
+    let repo = read_config(&repo_config_path);
+    let user = user_config_path()
+        .map(|path| read_config(&path))
+        .unwrap_or_default();
+    repo.merge(user)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn repo_config_wins_over_user_config() {
+        let repo = Config {
+            remote: Some("upstream".to_string()),
+            ..Default::default()
+        };
+        let user = Config {
+            remote: Some("origin".to_string()),
+            verbose: Some(true),
+            ..Default::default()
+        };
+        let merged = repo.merge(user);
+        assert_eq!(merged.remote.as_deref(), Some("upstream"));
+        assert_eq!(merged.verbose, Some(true));
+    }
+
+    #[test]
+    fn missing_fields_fall_back_to_lower_precedence() {
+        let repo = Config::default();
+        let user = Config {
+            branch: Some("trunk".to_string()),
+            ..Default::default()
+        };
+        let merged = repo.merge(user);
+        assert_eq!(merged.branch.as_deref(), Some("trunk"));
+    }
+}
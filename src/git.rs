@@ -0,0 +1,556 @@
+//! Git operations abstracted behind a [`Git`] trait.
+//!
+//! [`Git2Backend`] is backed by the `git2` crate and is preferred; it avoids
+//! brittle stderr string-matching (e.g. checking for `"nothing to commit"`)
+//! and works with repository states `git2` understands natively.
+//! [`ProcessBackend`] shells out to the `git` binary and is kept as a
+//! fallback for hosts where `git2` can't open the repository (e.g. exotic
+//! filesystems or submodule layouts libgit2 doesn't support).
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// The git operations `ga` needs, independent of how they're carried out.
+pub trait Git {
+    /// True if there is nothing staged or unstaged to commit.
+    fn tree_is_clean(&self) -> Result<bool, String>;
+
+    /// The currently checked-out branch name; an error when HEAD is detached.
+    fn current_branch(&self) -> Result<String, String>;
+
+    /// Paths with uncommitted changes (staged or not).
+    fn dirty_files(&self) -> Result<Vec<String>, String>;
+
+    /// True if `branch` exists, locally or as `<remote>/<branch>`.
+    fn has_branch(&self, remote: &str, branch: &str) -> Result<bool, String>;
+
+    /// True if `branch` has an upstream tracking branch configured.
+    fn has_upstream(&self, branch: &str) -> Result<bool, String>;
+
+    /// `remote`'s default branch, e.g. `main` for `refs/remotes/origin/HEAD`.
+    fn default_branch(&self, remote: &str) -> Result<String, String>;
+
+    fn stage_all(&self) -> Result<(), String>;
+
+    /// Commits staged changes with `message`, sign-ing off (`-s`) when `sign` is set.
+    fn commit(&self, message: &str, sign: bool) -> Result<(), String>;
+
+    /// Pushes `branch` to `remote`, passing `--set-upstream` when `set_upstream` is set.
+    fn push(&self, remote: &str, branch: &str, set_upstream: bool) -> Result<(), String>;
+
+    /// The most recent tag reachable from HEAD, or `None` if the repository
+    /// has no tags.
+    fn last_tag(&self) -> Option<String>;
+
+    /// Full commit messages reachable from HEAD, newest first, excluding
+    /// anything reachable from `tag` when given.
+    fn commits_since(&self, tag: Option<&str>) -> Result<Vec<String>, String>;
+}
+
+/// The repository's work tree root, e.g. for locating `.ga.toml` or
+/// `.git/hooks` regardless of the current working directory (a
+/// subdirectory, or a linked worktree where `.git` is a file, not a
+/// directory).
+pub fn discover_root() -> Option<PathBuf> {
+    if let Ok(repo) = git2::Repository::discover(".") {
+        if let Some(workdir) = repo.workdir() {
+            return Some(workdir.to_path_buf());
+        }
+    }
+
+    Command::new("git")
+        .args(["rev-parse", "--show-toplevel"])
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .map(|o| PathBuf::from(String::from_utf8_lossy(&o.stdout).trim()))
+}
+
+/// The repository's common git directory (`.git`, or the real directory it
+/// points to in a linked worktree), used for installing hooks.
+pub fn discover_git_dir() -> Option<PathBuf> {
+    if let Ok(repo) = git2::Repository::discover(".") {
+        return Some(repo.commondir().to_path_buf());
+    }
+
+    Command::new("git")
+        .args(["rev-parse", "--git-common-dir"])
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .map(|o| PathBuf::from(String::from_utf8_lossy(&o.stdout).trim()))
+}
+
+/// Opens the repository at the current directory, preferring the `git2`
+/// backend and falling back to shelling out to `git` if that fails.
+pub fn open() -> Result<Box<dyn Git>, String> {
+    match Git2Backend::open(Path::new(".")) {
+        Ok(backend) => Ok(Box::new(backend)),
+        Err(_) => {
+            if ProcessBackend.is_inside_work_tree() {
+                Ok(Box::new(ProcessBackend))
+            } else {
+                Err("Not a git repository (or any parent up to mount point)".to_string())
+            }
+        }
+    }
+}
+
+/// `git2`-backed implementation, used whenever libgit2 can open the repo.
+pub struct Git2Backend {
+    repo: git2::Repository,
+}
+
+impl Git2Backend {
+    fn open(path: &Path) -> Result<Self, git2::Error> {
+        let repo = git2::Repository::discover(path)?;
+        Ok(Self { repo })
+    }
+}
+
+impl Git for Git2Backend {
+    fn tree_is_clean(&self) -> Result<bool, String> {
+        let mut opts = git2::StatusOptions::new();
+        opts.include_untracked(true);
+        let statuses = self
+            .repo
+            .statuses(Some(&mut opts))
+            .map_err(|e| format!("Failed to read status: {}", e))?;
+        Ok(statuses.is_empty())
+    }
+
+    fn current_branch(&self) -> Result<String, String> {
+        if self
+            .repo
+            .head_detached()
+            .map_err(|e| format!("Failed to read HEAD: {}", e))?
+        {
+            return Err("HEAD is detached".to_string());
+        }
+        let head = self
+            .repo
+            .head()
+            .map_err(|e| format!("Failed to read HEAD: {}", e))?;
+        head.shorthand()
+            .map(|s| s.to_string())
+            .ok_or_else(|| "HEAD is not a valid UTF-8 branch name".to_string())
+    }
+
+    fn dirty_files(&self) -> Result<Vec<String>, String> {
+        let mut opts = git2::StatusOptions::new();
+        opts.include_untracked(true);
+        let statuses = self
+            .repo
+            .statuses(Some(&mut opts))
+            .map_err(|e| format!("Failed to read status: {}", e))?;
+        Ok(statuses
+            .iter()
+            .filter_map(|entry| entry.path().map(|p| p.to_string()))
+            .collect())
+    }
+
+    fn has_branch(&self, remote: &str, branch: &str) -> Result<bool, String> {
+        if self
+            .repo
+            .find_branch(branch, git2::BranchType::Local)
+            .is_ok()
+        {
+            return Ok(true);
+        }
+        let remote_branch = format!("{}/{}", remote, branch);
+        Ok(self
+            .repo
+            .find_branch(&remote_branch, git2::BranchType::Remote)
+            .is_ok())
+    }
+
+    fn has_upstream(&self, branch: &str) -> Result<bool, String> {
+        let local = self
+            .repo
+            .find_branch(branch, git2::BranchType::Local)
+            .map_err(|e| format!("Unknown branch `{}`: {}", branch, e))?;
+        Ok(local.upstream().is_ok())
+    }
+
+    fn default_branch(&self, remote: &str) -> Result<String, String> {
+        let head_ref = format!("refs/remotes/{}/HEAD", remote);
+        let reference = self
+            .repo
+            .find_reference(&head_ref)
+            .map_err(|e| format!("Failed to resolve {}: {}", head_ref, e))?;
+        let target = reference
+            .symbolic_target()
+            .ok_or_else(|| format!("{} is not a symbolic reference", head_ref))?;
+        target
+            .strip_prefix(&format!("refs/remotes/{}/", remote))
+            .map(|s| s.to_string())
+            .ok_or_else(|| format!("Unexpected target for {}: {}", head_ref, target))
+    }
+
+    fn stage_all(&self) -> Result<(), String> {
+        let mut index = self
+            .repo
+            .index()
+            .map_err(|e| format!("Failed to open index: {}", e))?;
+        index
+            .add_all(["."].iter(), git2::IndexAddOption::DEFAULT, None)
+            .map_err(|e| format!("Failed to stage changes: {}", e))?;
+        index
+            .write()
+            .map_err(|e| format!("Failed to write index: {}", e))
+    }
+
+    fn commit(&self, message: &str, sign: bool) -> Result<(), String> {
+        // Sign-off ("-s") trailers are a commit message convention, not a
+        // feature of libgit2 itself, so apply it here before handing the
+        // message to the object database.
+        let message = if sign {
+            format!("{}\n\nSigned-off-by: {}", message, signature_line(&self.repo)?)
+        } else {
+            message.to_string()
+        };
+
+        let mut index = self
+            .repo
+            .index()
+            .map_err(|e| format!("Failed to open index: {}", e))?;
+        let tree_id = index
+            .write_tree()
+            .map_err(|e| format!("Failed to write tree: {}", e))?;
+        let tree = self
+            .repo
+            .find_tree(tree_id)
+            .map_err(|e| format!("Failed to find tree: {}", e))?;
+        let signature = self
+            .repo
+            .signature()
+            .map_err(|e| format!("Failed to build signature: {}", e))?;
+
+        // A brand new repository has no HEAD commit yet ("unborn branch");
+        // that's not an error, it just means this commit has no parents.
+        match self.repo.head() {
+            Ok(head) => {
+                let parent = head
+                    .peel_to_commit()
+                    .map_err(|e| format!("Failed to resolve parent commit: {}", e))?;
+                if parent.tree_id() == tree_id {
+                    return Err("Nothing to commit, working tree clean".to_string());
+                }
+                self.repo.commit(
+                    Some("HEAD"),
+                    &signature,
+                    &signature,
+                    &message,
+                    &tree,
+                    &[&parent],
+                )
+            }
+            Err(e) if e.code() == git2::ErrorCode::UnbornBranch => self.repo.commit(
+                Some("HEAD"),
+                &signature,
+                &signature,
+                &message,
+                &tree,
+                &[],
+            ),
+            Err(e) => return Err(format!("Failed to read HEAD: {}", e)),
+        }
+        .map_err(|e| format!("git commit failed: {}", e))?;
+        Ok(())
+    }
+
+    fn push(&self, remote: &str, branch: &str, set_upstream: bool) -> Result<(), String> {
+        let mut remote = self
+            .repo
+            .find_remote(remote)
+            .map_err(|e| format!("Unknown remote: {}", e))?;
+        let refspec = format!("refs/heads/{branch}:refs/heads/{branch}");
+
+        let mut callbacks = git2::RemoteCallbacks::new();
+        callbacks.credentials(|url, username_from_url, allowed_types| {
+            if allowed_types.contains(git2::CredentialType::SSH_KEY) {
+                if let Some(username) = username_from_url {
+                    if let Ok(cred) = git2::Cred::ssh_key_from_agent(username) {
+                        return Ok(cred);
+                    }
+                }
+            }
+            git2::Cred::credential_helper(&self.repo.config()?, url, username_from_url)
+        });
+        let mut push_opts = git2::PushOptions::new();
+        push_opts.remote_callbacks(callbacks);
+
+        remote
+            .push(&[&refspec], Some(&mut push_opts))
+            .map_err(|e| format!("git push failed: {}", e))?;
+
+        if set_upstream {
+            self.repo
+                .config()
+                .and_then(|mut cfg| {
+                    cfg.set_str(&format!("branch.{branch}.remote"), remote.name().unwrap_or(""))?;
+                    cfg.set_str(&format!("branch.{branch}.merge"), &format!("refs/heads/{branch}"))
+                })
+                .map_err(|e| format!("Failed to record upstream: {}", e))?;
+        }
+        Ok(())
+    }
+
+    fn last_tag(&self) -> Option<String> {
+        let mut opts = git2::DescribeOptions::new();
+        opts.describe_tags();
+        let describe = self.repo.describe(&opts).ok()?;
+        // `abbreviated_size(0)` drops the `-<n>-g<sha>` suffix, matching
+        // `git describe --tags --abbrev=0`: just the nearest tag name.
+        describe
+            .format(Some(git2::DescribeFormatOptions::new().abbreviated_size(0)))
+            .ok()
+    }
+
+    fn commits_since(&self, tag: Option<&str>) -> Result<Vec<String>, String> {
+        let mut revwalk = self
+            .repo
+            .revwalk()
+            .map_err(|e| format!("Failed to walk commit history: {}", e))?;
+        revwalk
+            .push_head()
+            .map_err(|e| format!("Failed to start walk at HEAD: {}", e))?;
+        if let Some(tag) = tag {
+            let target = self
+                .repo
+                .revparse_single(tag)
+                .map_err(|e| format!("Unknown tag `{}`: {}", tag, e))?;
+            revwalk
+                .hide(target.id())
+                .map_err(|e| format!("Failed to exclude {}: {}", tag, e))?;
+        }
+
+        revwalk
+            .map(|oid| {
+                let oid = oid.map_err(|e| format!("Failed to walk commit history: {}", e))?;
+                let commit = self
+                    .repo
+                    .find_commit(oid)
+                    .map_err(|e| format!("Failed to read commit {}: {}", oid, e))?;
+                Ok(commit.message().unwrap_or_default().trim().to_string())
+            })
+            .filter(|message| !matches!(message, Ok(s) if s.is_empty()))
+            .collect()
+    }
+}
+
+fn signature_line(repo: &git2::Repository) -> Result<String, String> {
+    let sig = repo
+        .signature()
+        .map_err(|e| format!("Failed to build signature: {}", e))?;
+    Ok(format!(
+        "{} <{}>",
+        sig.name().unwrap_or("unknown"),
+        sig.email().unwrap_or("")
+    ))
+}
+
+/// Shells out to the `git` binary. This is the original implementation,
+/// kept as a fallback for environments `git2` can't handle.
+pub struct ProcessBackend;
+
+impl ProcessBackend {
+    fn is_inside_work_tree(&self) -> bool {
+        Command::new("git")
+            .args(["rev-parse", "--is-inside-work-tree"])
+            .output()
+            .map(|o| o.status.success() && String::from_utf8_lossy(&o.stdout).trim() == "true")
+            .unwrap_or(false)
+    }
+}
+
+impl Git for ProcessBackend {
+    fn tree_is_clean(&self) -> Result<bool, String> {
+        let output = Command::new("git")
+            .args(["status", "--porcelain"])
+            .output()
+            .map_err(|e| format!("Failed to execute git status: {}", e))?;
+        if !output.status.success() {
+            return Err(format!(
+                "git status failed: {}",
+                String::from_utf8_lossy(&output.stderr)
+            ));
+        }
+        Ok(output.stdout.is_empty())
+    }
+
+    fn current_branch(&self) -> Result<String, String> {
+        let output = Command::new("git")
+            .args(["rev-parse", "--abbrev-ref", "HEAD"])
+            .output()
+            .map_err(|e| format!("Failed to execute git rev-parse: {}", e))?;
+        if !output.status.success() {
+            return Err(format!(
+                "git rev-parse failed: {}",
+                String::from_utf8_lossy(&output.stderr)
+            ));
+        }
+        let branch = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        if branch == "HEAD" {
+            return Err("HEAD is detached".to_string());
+        }
+        Ok(branch)
+    }
+
+    fn dirty_files(&self) -> Result<Vec<String>, String> {
+        let output = Command::new("git")
+            .args(["status", "--porcelain"])
+            .output()
+            .map_err(|e| format!("Failed to execute git status: {}", e))?;
+        if !output.status.success() {
+            return Err(format!(
+                "git status failed: {}",
+                String::from_utf8_lossy(&output.stderr)
+            ));
+        }
+        Ok(String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter_map(|line| line.get(3..).map(|p| p.to_string()))
+            .collect())
+    }
+
+    fn has_branch(&self, remote: &str, branch: &str) -> Result<bool, String> {
+        let local = Command::new("git")
+            .args(["show-ref", "--verify", "--quiet", &format!("refs/heads/{branch}")])
+            .status()
+            .map_err(|e| format!("Failed to execute git show-ref: {}", e))?;
+        if local.success() {
+            return Ok(true);
+        }
+        let remote_ref = format!("refs/remotes/{remote}/{branch}");
+        let remote_exists = Command::new("git")
+            .args(["show-ref", "--verify", "--quiet", &remote_ref])
+            .status()
+            .map_err(|e| format!("Failed to execute git show-ref: {}", e))?;
+        Ok(remote_exists.success())
+    }
+
+    fn has_upstream(&self, branch: &str) -> Result<bool, String> {
+        let status = Command::new("git")
+            .args(["rev-parse", "--abbrev-ref", "--symbolic-full-name", &format!("{branch}@{{upstream}}")])
+            .output()
+            .map_err(|e| format!("Failed to execute git rev-parse: {}", e))?;
+        Ok(status.status.success())
+    }
+
+    fn default_branch(&self, remote: &str) -> Result<String, String> {
+        let output = Command::new("git")
+            .args(["symbolic-ref", &format!("refs/remotes/{remote}/HEAD")])
+            .output()
+            .map_err(|e| format!("Failed to execute git symbolic-ref: {}", e))?;
+        if !output.status.success() {
+            return Err(format!(
+                "Failed to resolve default branch for {}: {}",
+                remote,
+                String::from_utf8_lossy(&output.stderr)
+            ));
+        }
+        let target = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        target
+            .strip_prefix(&format!("refs/remotes/{remote}/"))
+            .map(|s| s.to_string())
+            .ok_or_else(|| format!("Unexpected default branch ref: {}", target))
+    }
+
+    fn stage_all(&self) -> Result<(), String> {
+        let output = Command::new("git")
+            .args(["add", "."])
+            .output()
+            .map_err(|e| format!("Failed to execute git add: {}", e))?;
+        if !output.status.success() {
+            return Err(format!(
+                "git add failed: {}",
+                String::from_utf8_lossy(&output.stderr)
+            ));
+        }
+        Ok(())
+    }
+
+    fn commit(&self, message: &str, sign: bool) -> Result<(), String> {
+        let mut cmd = Command::new("git");
+        cmd.arg("commit");
+        if sign {
+            cmd.arg("-s");
+        }
+        cmd.arg("-m").arg(message);
+        let output = cmd
+            .output()
+            .map_err(|e| format!("Failed to execute git commit: {}", e))?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            if stderr.contains("nothing to commit") {
+                return Err("Nothing to commit, working tree clean".to_string());
+            }
+            return Err(format!("git commit failed: {}", stderr));
+        }
+        Ok(())
+    }
+
+    fn push(&self, remote: &str, branch: &str, set_upstream: bool) -> Result<(), String> {
+        let mut cmd = Command::new("git");
+        cmd.arg("push");
+        if set_upstream {
+            cmd.arg("--set-upstream");
+        }
+        cmd.arg(remote).arg(branch);
+        let output = cmd
+            .output()
+            .map_err(|e| format!("Failed to execute git push: {}", e))?;
+
+        if !output.status.success() {
+            return Err(format!(
+                "git push failed: {}",
+                String::from_utf8_lossy(&output.stderr)
+            ));
+        }
+        Ok(())
+    }
+
+    fn last_tag(&self) -> Option<String> {
+        let output = Command::new("git")
+            .args(["describe", "--tags", "--abbrev=0"])
+            .output()
+            .ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        let tag = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        if tag.is_empty() {
+            None
+        } else {
+            Some(tag)
+        }
+    }
+
+    fn commits_since(&self, tag: Option<&str>) -> Result<Vec<String>, String> {
+        let range = match tag {
+            Some(tag) => format!("{}..HEAD", tag),
+            None => "HEAD".to_string(),
+        };
+
+        let output = Command::new("git")
+            .args(["log", &range, "--pretty=format:%B%x1e"])
+            .output()
+            .map_err(|e| format!("Failed to execute git log: {}", e))?;
+
+        if !output.status.success() {
+            return Err(format!(
+                "git log failed: {}",
+                String::from_utf8_lossy(&output.stderr)
+            ));
+        }
+
+        let raw = String::from_utf8_lossy(&output.stdout);
+        Ok(raw
+            .split('\x1e')
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+            .collect())
+    }
+}
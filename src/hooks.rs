@@ -0,0 +1,36 @@
+//! Installs a `commit-msg` git hook that runs `ga`'s commit-message linter,
+//! so the same rules apply to commits made through plain `git commit`.
+
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+
+const HOOK_SCRIPT: &str = "#!/bin/sh\nexec ga lint-commit-msg \"$1\"\n";
+
+/// Writes `<git-common-dir>/hooks/commit-msg`, refusing to clobber an
+/// existing hook unless `force` is set. Resolves the git directory through
+/// `git2` rather than assuming `.git/hooks` so this works from a
+/// subdirectory or inside a linked worktree (where `.git` is a file).
+pub fn install(force: bool) -> Result<(), String> {
+    let git_dir = crate::git::discover_git_dir()
+        .ok_or_else(|| "Not a git repository (or any parent up to mount point)".to_string())?;
+    let hook_path = git_dir.join("hooks").join("commit-msg");
+
+    if hook_path.exists() && !force {
+        return Err(format!(
+            "{} already exists; pass --force to overwrite",
+            hook_path.display()
+        ));
+    }
+
+    fs::write(&hook_path, HOOK_SCRIPT)
+        .map_err(|e| format!("Failed to write {}: {}", hook_path.display(), e))?;
+
+    let mut perms = fs::metadata(&hook_path)
+        .map_err(|e| format!("Failed to read metadata for {}: {}", hook_path.display(), e))?
+        .permissions();
+    perms.set_mode(0o755);
+    fs::set_permissions(&hook_path, perms)
+        .map_err(|e| format!("Failed to make {} executable: {}", hook_path.display(), e))?;
+
+    Ok(())
+}
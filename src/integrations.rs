@@ -0,0 +1,154 @@
+//! Optional post-push integrations: emailing the pushed commits as a patch
+//! series, or opening a pull request against the upstream host. Both are
+//! opt-in (`--send-email` / `--pr`) and skip cleanly when there is nothing
+//! new to send.
+
+use crate::config::{EmailConfig, PrConfig};
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+fn format_patch_series(upstream: &str) -> Result<String, String> {
+    let output = Command::new("git")
+        .args(["format-patch", "--stdout", &format!("{}..HEAD", upstream)])
+        .output()
+        .map_err(|e| format!("git format-patch: failed to execute: {}", e))?;
+
+    if !output.status.success() {
+        return Err(format!(
+            "git format-patch: failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        ));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// Formats commits not yet on `<remote>/<branch>` as a patch series and
+/// pipes them to `sendmail`. Does nothing if there are no such commits.
+pub fn send_email(remote: &str, branch: &str, config: &EmailConfig) -> Result<(), String> {
+    let upstream = format!("{}/{}", remote, branch);
+    let patches = format_patch_series(&upstream)?;
+    if patches.is_empty() {
+        println!("No commits ahead of {}, nothing to email.", upstream);
+        return Ok(());
+    }
+
+    let from = config
+        .from
+        .clone()
+        .ok_or_else(|| "sendmail: email.from is not configured in .ga.toml".to_string())?;
+    let to = config
+        .to
+        .clone()
+        .filter(|recipients| !recipients.is_empty())
+        .ok_or_else(|| "sendmail: email.to is not configured in .ga.toml".to_string())?;
+
+    let mut child = Command::new("sendmail")
+        .arg("-f")
+        .arg(&from)
+        .args(&to)
+        .stdin(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("sendmail: failed to spawn: {}", e))?;
+
+    let stdin = child
+        .stdin
+        .as_mut()
+        .ok_or_else(|| "sendmail: failed to open stdin".to_string())?;
+    stdin
+        .write_all(patches.as_bytes())
+        .map_err(|e| format!("sendmail: failed to write patch series: {}", e))?;
+    drop(child.stdin.take());
+
+    let status = child
+        .wait()
+        .map_err(|e| format!("sendmail: failed to wait for exit: {}", e))?;
+    if !status.success() {
+        return Err(format!("sendmail: exited with {}", status));
+    }
+
+    Ok(())
+}
+
+fn remote_owner_and_repo(remote: &str) -> Result<(String, String), String> {
+    let output = Command::new("git")
+        .args(["remote", "get-url", remote])
+        .output()
+        .map_err(|e| format!("git remote get-url: failed to execute: {}", e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "git remote get-url: failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        ));
+    }
+
+    let url = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    parse_github_owner_repo(&url)
+        .ok_or_else(|| format!("pr: could not parse owner/repo from remote url `{}`", url))
+}
+
+fn parse_github_owner_repo(url: &str) -> Option<(String, String)> {
+    let trimmed = url.trim_end_matches(".git");
+    let (_, path) = trimmed.rsplit_once("github.com")?;
+    let path = path.trim_start_matches([':', '/']);
+    let (owner, repo) = path.split_once('/')?;
+    Some((owner.to_string(), repo.to_string()))
+}
+
+/// Opens a pull request for `head_branch` (the branch that was just
+/// pushed) against `remote`'s GitHub host, using a token read from the
+/// environment variable configured in `config` (`GITHUB_TOKEN` by
+/// default). The target branch is `config.base` when set, otherwise
+/// `remote`'s default branch. Does nothing if there are no commits ahead
+/// of `<remote>/<base>`.
+pub fn open_pull_request(remote: &str, head_branch: &str, config: &PrConfig) -> Result<(), String> {
+    let base = match config.base.clone() {
+        Some(base) => base,
+        None => crate::git::open()?.default_branch(remote)?,
+    };
+    if base == head_branch {
+        return Err(format!(
+            "pr: base branch `{}` is the same as the head branch; set pr.base in .ga.toml",
+            base
+        ));
+    }
+
+    let upstream = format!("{}/{}", remote, base);
+    if format_patch_series(&upstream)?.is_empty() {
+        println!("No commits ahead of {}, skipping pull request.", upstream);
+        return Ok(());
+    }
+
+    let token_env = config
+        .token_env
+        .clone()
+        .unwrap_or_else(|| "GITHUB_TOKEN".to_string());
+    let token = std::env::var(&token_env)
+        .map_err(|_| format!("pr: {} is not set in the environment", token_env))?;
+
+    let (owner, repo) = remote_owner_and_repo(remote)?;
+
+    let client = reqwest::blocking::Client::new();
+    let response = client
+        .post(format!(
+            "https://api.github.com/repos/{}/{}/pulls",
+            owner, repo
+        ))
+        .bearer_auth(token)
+        .header("User-Agent", "ga-cli")
+        .json(&serde_json::json!({
+            "title": head_branch,
+            "head": head_branch,
+            "base": base,
+        }))
+        .send()
+        .map_err(|e| format!("pr: request to GitHub failed: {}", e))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().unwrap_or_default();
+        return Err(format!("pr: GitHub API returned {}: {}", status, body));
+    }
+
+    Ok(())
+}
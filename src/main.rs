@@ -1,8 +1,18 @@
-use clap::Parser;
+mod changelog;
+mod commit_lint;
+mod completions;
+mod config;
+mod git;
+mod hooks;
+mod integrations;
+
+use clap::{Parser, Subcommand};
+use clap_complete::Shell;
 use colored::Colorize;
-use dialoguer::Input;
-use std::path::Path;
-use std::process::{Command, ExitCode};
+use commit_lint::LintRules;
+use git::Git;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
 
 /// A Git automation CLI that handles add, commit, and push operations
 #[derive(Parser, Debug)]
@@ -10,40 +20,119 @@ use std::process::{Command, ExitCode};
 #[command(version = "0.1.0")]
 #[command(about = "Automates Git add, commit, and push workflow", long_about = None)]
 struct Args {
+    #[command(subcommand)]
+    command: Option<SubCommand>,
+
     /// Commit message (if not provided, you'll be prompted)
     #[arg(short, long)]
     message: Option<String>,
 
-    /// The branch origin to push to (if not provided, code is pushed to main)
+    /// The branch to push to (if not provided, `branch` from .ga.toml is used,
+    /// then the current branch, falling back to the remote's default branch)
     #[arg(short, long)]
     origin: Option<String>,
 
+    /// The remote to push to (default: "origin", overridable in .ga.toml)
+    #[arg(short, long)]
+    remote: Option<String>,
+
     /// Print verbose output from git commands
     #[arg(short, long)]
     verbose: bool,
+
+    /// Sign off commits with -s (default: true, overridable in .ga.toml)
+    #[arg(short, long)]
+    sign: bool,
+
+    /// Skip conventional-commit message linting
+    #[arg(long)]
+    no_verify: bool,
+
+    /// Generate or update CHANGELOG.md from commits since the last tag, then exit
+    #[arg(long)]
+    changelog: bool,
+
+    /// After pushing, email the new commits as a patch series
+    #[arg(long)]
+    send_email: bool,
+
+    /// After pushing, open a pull request against the upstream host
+    #[arg(long)]
+    pr: bool,
+}
+
+#[derive(Subcommand, Debug)]
+enum SubCommand {
+    /// Print a shell completion script to stdout
+    Completions {
+        /// Shell to generate completions for
+        shell: Shell,
+    },
+    /// Install a commit-msg hook that runs ga's commit-message linter
+    InstallHook {
+        /// Overwrite an existing commit-msg hook
+        #[arg(long)]
+        force: bool,
+    },
+    /// Lint the commit message in a file (used by the installed commit-msg hook)
+    #[command(hide = true)]
+    LintCommitMsg { path: PathBuf },
 }
 
 fn main() -> ExitCode {
     let args = Args::parse();
 
-    // Check if we're in a git repository
-    if !Path::new(".git").exists() {
-        eprintln!(
-            "{} {}",
-            "Error:".red().bold(),
-            "Not a git repository. No .git directory found."
-        );
-        return ExitCode::FAILURE;
+    if let Some(command) = &args.command {
+        return run_subcommand(command);
+    }
+
+    let config = config::load();
+
+    // Precedence: CLI flag > repo config > user config > built-in default
+    let remote = args
+        .remote
+        .or(config.remote.clone())
+        .unwrap_or_else(|| "origin".to_string());
+    let verbose = args.verbose || config.verbose.unwrap_or(false);
+    let sign = args.sign || config.sign.unwrap_or(true);
+
+    // Open the repository, preferring libgit2 over shelling out to `git`
+    let repo = match git::open() {
+        Ok(repo) => repo,
+        Err(e) => {
+            eprintln!("{} {}", "Error:".red().bold(), e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    if args.changelog {
+        let header = config.changelog.header.unwrap_or_default();
+        let footer = config.changelog.footer.unwrap_or_default();
+        return match changelog::update(repo.as_ref(), Path::new("CHANGELOG.md"), &header, &footer) {
+            Ok(()) => {
+                println!(
+                    "{} {}",
+                    "✓".green().bold(),
+                    "Updated CHANGELOG.md".green()
+                );
+                ExitCode::SUCCESS
+            }
+            Err(e) => {
+                eprintln!("{} {}", "Error:".red().bold(), e);
+                ExitCode::FAILURE
+            }
+        };
     }
 
     // Step 1: git add .
-    if let Err(e) = run_git_add(args.verbose) {
+    if let Err(e) = run_git_add(repo.as_ref(), verbose) {
         eprintln!("{} {}", "Error:".red().bold(), e);
         return ExitCode::FAILURE;
     }
 
     // Step 2: Get commit message
-    let message = match get_commit_message(args.message) {
+    let lint_rules = LintRules::from_config(&config.lint);
+    let message = match get_commit_message(args.message, &lint_rules) {
         Ok(msg) => msg,
         Err(e) => {
             eprintln!("{} {}", "Error:".red().bold(), e);
@@ -51,15 +140,37 @@ fn main() -> ExitCode {
         }
     };
 
+    // Step 2.5: Lint the commit message against the conventional-commit rules
+    if !args.no_verify {
+        let errors = commit_lint::lint(&message, &lint_rules);
+        if !errors.is_empty() {
+            eprintln!("{} {}", "Error:".red().bold(), "Commit message failed linting:".red());
+            for err in &errors {
+                eprintln!("  {} {}", "-".red(), err);
+            }
+            eprintln!(
+                "{}",
+                "Pass --no-verify to bypass the commit message linter.".yellow()
+            );
+            return ExitCode::FAILURE;
+        }
+    }
+
     // Step 3: git commit
-    if let Err(e) = run_git_commit(&message, args.verbose) {
+    if let Err(e) = run_git_commit(repo.as_ref(), &message, verbose, sign) {
         eprintln!("{} {}", "Error:".red().bold(), e);
         return ExitCode::FAILURE;
     }
 
     // Step 4: Determine branch and push
-    let branch = args.origin.unwrap_or_else(|| "main".to_string());
-    if let Err(e) = run_git_push(&branch, args.verbose) {
+    let branch = match args.origin.or(config.branch.clone()) {
+        Some(branch) => branch,
+        None => repo
+            .current_branch()
+            .or_else(|_| repo.default_branch(&remote))
+            .unwrap_or_else(|_| "main".to_string()),
+    };
+    if let Err(e) = run_git_push(repo.as_ref(), &remote, &branch, verbose) {
         eprintln!("{} {}", "Error:".red().bold(), e);
         return ExitCode::FAILURE;
     }
@@ -70,34 +181,83 @@ fn main() -> ExitCode {
         "Successfully pushed the code!".green()
     );
 
+    if args.send_email {
+        if let Err(e) = integrations::send_email(&remote, &branch, &config.email) {
+            eprintln!("{} {}", "Error:".red().bold(), e);
+            return ExitCode::FAILURE;
+        }
+    }
+
+    if args.pr {
+        if let Err(e) = integrations::open_pull_request(&remote, &branch, &config.pr) {
+            eprintln!("{} {}", "Error:".red().bold(), e);
+            return ExitCode::FAILURE;
+        }
+    }
+
     ExitCode::SUCCESS
 }
 
-fn run_git_add(verbose: bool) -> Result<(), String> {
-    println!("{} {}", "→".blue().bold(), "Running git add .".cyan());
+fn run_subcommand(command: &SubCommand) -> ExitCode {
+    match command {
+        SubCommand::Completions { shell } => {
+            completions::print(*shell);
+            ExitCode::SUCCESS
+        }
+        SubCommand::InstallHook { force } => match hooks::install(*force) {
+            Ok(()) => {
+                println!(
+                    "{} {}",
+                    "✓".green().bold(),
+                    "Installed commit-msg hook".green()
+                );
+                ExitCode::SUCCESS
+            }
+            Err(e) => {
+                eprintln!("{} {}", "Error:".red().bold(), e);
+                ExitCode::FAILURE
+            }
+        },
+        SubCommand::LintCommitMsg { path } => {
+            let message = match std::fs::read_to_string(path) {
+                Ok(message) => message,
+                Err(e) => {
+                    eprintln!("{} Failed to read {}: {}", "Error:".red().bold(), path.display(), e);
+                    return ExitCode::FAILURE;
+                }
+            };
 
-    let output = Command::new("git")
-        .arg("add")
-        .arg(".")
-        .output()
-        .map_err(|e| format!("Failed to execute git add: {}", e))?;
-
-    if !output.status.success() {
-        return Err(format!(
-            "git add failed: {}",
-            String::from_utf8_lossy(&output.stderr)
-        ));
+            let config = config::load();
+            let errors = commit_lint::lint(&message, &LintRules::from_config(&config.lint));
+            if errors.is_empty() {
+                ExitCode::SUCCESS
+            } else {
+                eprintln!("{} {}", "Error:".red().bold(), "Commit message failed linting:".red());
+                for err in &errors {
+                    eprintln!("  {} {}", "-".red(), err);
+                }
+                ExitCode::FAILURE
+            }
+        }
     }
+}
+
+fn run_git_add(repo: &dyn Git, verbose: bool) -> Result<(), String> {
+    println!("{} {}", "→".blue().bold(), "Running git add .".cyan());
 
-    if verbose && !output.stdout.is_empty() {
-        println!("{}", String::from_utf8_lossy(&output.stdout));
+    if verbose {
+        for path in repo.dirty_files()? {
+            println!("  {}", path);
+        }
     }
 
+    repo.stage_all()?;
+
     println!("{} {}", "✓".green().bold(), "Staged all changes".green());
     Ok(())
 }
 
-fn get_commit_message(message: Option<String>) -> Result<String, String> {
+fn get_commit_message(message: Option<String>, rules: &LintRules) -> Result<String, String> {
     match message {
         Some(msg) => {
             if msg.trim().is_empty() {
@@ -109,91 +269,48 @@ fn get_commit_message(message: Option<String>) -> Result<String, String> {
             println!(
                 "{} {}",
                 "→".blue().bold(),
-                "Commit message required".cyan()
+                "Commit message required, let's build one together".cyan()
             );
-            Input::<String>::new()
-                .with_prompt("Enter commit message")
-                .interact_text()
-                .map_err(|e| format!("Failed to read input: {}", e))
-                .and_then(|msg| {
-                    if msg.trim().is_empty() {
-                        Err("Commit message cannot be empty".to_string())
-                    } else {
-                        Ok(msg)
-                    }
-                })
+            commit_lint::build_message_interactively(rules)
         }
     }
 }
 
-fn run_git_commit(message: &str, verbose: bool) -> Result<(), String> {
+fn run_git_commit(repo: &dyn Git, message: &str, verbose: bool, sign: bool) -> Result<(), String> {
     println!(
         "{} {}",
         "→".blue().bold(),
         format!("Committing with message: \"{}\"", message).cyan()
     );
 
-    let output = Command::new("git")
-        .arg("commit")
-        .arg("-s")
-        .arg("-m")
-        .arg(message)
-        .output()
-        .map_err(|e| format!("Failed to execute git commit: {}", e))?;
-
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        // Check if there's nothing to commit
-        if stderr.contains("nothing to commit") {
-            return Err("Nothing to commit, working tree clean".to_string());
-        }
-        return Err(format!("git commit failed: {}", stderr));
+    if verbose && repo.tree_is_clean()? {
+        println!("{}", "Nothing staged, commit will likely fail".yellow());
     }
 
-    if verbose {
-        println!("{}", String::from_utf8_lossy(&output.stdout));
-    }
+    repo.commit(message, sign)?;
 
     println!("{} {}", "✓".green().bold(), "Commit created".green());
     Ok(())
 }
 
-fn run_git_push(branch: &str, verbose: bool) -> Result<(), String> {
+fn run_git_push(repo: &dyn Git, remote: &str, branch: &str, verbose: bool) -> Result<(), String> {
     println!(
         "{} {}",
         "→".blue().bold(),
-        format!("Pushing to origin/{}", branch).cyan()
+        format!("Pushing to {}/{}", remote, branch).cyan()
     );
 
-    let output = Command::new("git")
-        .arg("push")
-        .arg("origin")
-        .arg(branch)
-        .output()
-        .map_err(|e| format!("Failed to execute git push: {}", e))?;
-
-    if !output.status.success() {
-        return Err(format!(
-            "git push failed: {}",
-            String::from_utf8_lossy(&output.stderr)
-        ));
+    let set_upstream = !repo.has_upstream(branch).unwrap_or(false);
+    if verbose && set_upstream {
+        println!("  no upstream configured, pushing with --set-upstream");
     }
 
-    if verbose {
-        let stdout = String::from_utf8_lossy(&output.stdout);
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        if !stdout.is_empty() {
-            println!("{}", stdout);
-        }
-        if !stderr.is_empty() {
-            println!("{}", stderr);
-        }
-    }
+    repo.push(remote, branch, set_upstream)?;
 
     println!(
         "{} {}",
         "✓".green().bold(),
-        format!("Pushed to origin/{}", branch).green()
+        format!("Pushed to {}/{}", remote, branch).green()
     );
     Ok(())
 }